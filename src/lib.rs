@@ -5,11 +5,20 @@ use pad::{PadStr, Alignment};
 use chrono::prelude::*;
 use serde::Serialize;
 use serde_json::to_string;
+use serde_json::Value;
 use std::io::Write;
 use std::fs::OpenOptions;
 use backtrace::Backtrace;
 use regex::Regex;
 use std::env;
+use std::collections::VecDeque;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+use std::thread::JoinHandle;
+use chrono::Duration;
 
 #[derive(Clone, Copy)]
 pub enum LogLevel {
@@ -24,6 +33,305 @@ pub enum LogLevel {
 pub struct Logger {
     log_level:        LogLevel,
     log_file:         String,
+    format:           Option<Format>,
+    memory:           Option<Memory>,
+    sender:           Option<SyncSender<Command>>,
+    policy:           OverflowPolicy,
+    dropped:          Arc<AtomicUsize>,
+    worker:           Option<JoinHandle<()>>,
+    rotation:         Option<Rotation>,
+    tag_levels:       HashMap<String, LogLevel>,
+    output:           LogFormat,
+    #[cfg(all(unix, feature = "syslog"))]
+    syslog:           Option<Syslog>,
+}
+
+/*
+    @brief Settings for the local syslog drain.
+
+    Holds the `openlog` identity so its backing string outlives the call (the C
+    library only borrows the pointer) and the facility masked into every
+    priority. Mirrors the local-syslog backend slog-syslog grew.
+*/
+#[cfg(all(unix, feature = "syslog"))]
+struct Syslog {
+    // Retained solely to keep the `CString` alive: `openlog` stores the raw
+    // pointer rather than copying it, so dropping this would dangle it.
+    #[allow(dead_code)]
+    ident:    std::ffi::CString,
+    facility: libc::c_int,
+}
+
+#[cfg(all(unix, feature = "syslog"))]
+thread_local! {
+    /*
+        A per-thread scratch buffer for the ANSI-stripped line, reused across
+        calls. Stripping and the C-string conversion still allocate per call.
+    */
+    static SYSLOG_BUF: std::cell::RefCell<String> = std::cell::RefCell::new(String::new());
+}
+
+/*
+    @brief How a record is rendered.
+
+    `Pretty` is the default ANSI tree; `Json` emits one JSON object per line
+    for machine ingestion, giving the crate the human-and-machine duality slog
+    emphasises.
+*/
+#[derive(Clone, Copy)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+/*
+    @brief Size-based rotation settings for the log file.
+
+    Keeps a running byte count so the common case costs no `metadata()`
+    syscall; when appending a line would push the file past `max_bytes`, the
+    file is rolled over (`app.log` → `app.log.1`, shifting the rest up to
+    `max_files` and dropping the oldest).
+*/
+struct Rotation {
+    max_bytes: u64,
+    max_files: u32,
+    size:      Mutex<u64>,
+}
+
+/*
+    @brief What to do when the async channel is full.
+
+    `Block` applies back-pressure to the logging thread; `Drop` discards the
+    record and bumps a counter exposed through `dropped_count`.
+*/
+#[derive(Clone, Copy)]
+pub enum OverflowPolicy {
+    Block,
+    Drop,
+}
+
+/*
+    @brief An owned logging job handed to the background writer thread.
+
+    Formatting and ANSI-stripping happen on the writer thread; only cheap,
+    owned data crosses the channel. The caller info is captured up front
+    because it depends on the originating thread's backtrace.
+*/
+struct MultiJob {
+    message: String,
+    tag:     String,
+    level:   LogLevel,
+    at:      bool,
+    object:  Option<String>,
+    callee:  Option<String>,
+    format:  Option<Format>,
+    output:  LogFormat,
+    #[cfg(all(unix, feature = "syslog"))]
+    syslog:  Option<libc::c_int>,
+}
+
+/*
+    @brief The single-line counterpart to `MultiJob`.
+*/
+struct SingleJob {
+    message: String,
+    tag:     String,
+    level:   LogLevel,
+    format:  Option<Format>,
+    output:  LogFormat,
+    #[cfg(all(unix, feature = "syslog"))]
+    syslog:  Option<libc::c_int>,
+}
+
+/*
+    @brief A unit of work for the background writer thread.
+*/
+enum Command {
+    Multi(MultiJob),
+    Single(SingleJob),
+    Flush(std::sync::mpsc::Sender<()>),
+}
+
+/*
+    @brief One retained log entry, stored uncoloured.
+
+    Captures the structured pieces `write` already computes so recent records
+    can be queried programmatically (for a status endpoint or test
+    assertions) rather than only hitting stdout or a file.
+*/
+#[derive(Clone)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Local>,
+    pub level:     LogLevel,
+    pub tag:       String,
+    pub message:   String,
+    pub object:    Option<String>,
+    pub callee:    Option<String>,
+}
+
+/*
+    @brief The in-memory ring buffer behind `Logger::with_memory`.
+
+    Holds at most `capacity` records and drops anything older than `keep`.
+    The buffer is wrapped in a `Mutex` so logging stays usable through a
+    shared `&Logger`.
+*/
+struct Memory {
+    buffer:   Mutex<VecDeque<LogRecord>>,
+    capacity: usize,
+    keep:     Duration,
+}
+
+/*
+    @brief A query over retained records.
+
+    `level` keeps records at or above the given severity, `module` matches the
+    domain tag, `regex` is matched against the plain message, `not_before`
+    drops anything older than the given instant and `limit` caps how many of
+    the most recent matches are returned.
+*/
+pub struct RecordFilter {
+    pub level:      LogLevel,
+    pub module:     Option<String>,
+    pub regex:      Option<Regex>,
+    pub not_before: Option<DateTime<Local>>,
+    pub limit:      u32,
+}
+
+/*
+    @brief A single piece of a custom log line.
+
+    Each variant maps to one of the fields the writer already knows how to
+    render. `Literal` carries its own text verbatim so callers can insert
+    spaces, brackets or any other decoration between the real fields.
+*/
+#[derive(Clone)]
+pub enum FormatToken {
+    Time,
+    Level,
+    Tag,
+    Message,
+    Caller,
+    Connector,
+    Literal(String),
+}
+
+/*
+    @brief An ordered recipe for the main log line.
+
+    Built through `Format::builder()`, a `Format` is just an ordered list of
+    `FormatToken`s that `write`/`write_single` walk in order, applying the
+    same colour/pad logic they use for the default layout. When a `Logger`
+    has no `Format` set the original hard-coded layout is used instead.
+*/
+#[derive(Clone)]
+pub struct Format {
+    tokens: Vec<FormatToken>,
+}
+
+/*
+    @brief Fluent builder for a `Format`.
+
+    Mirrors simplelog's custom-format support: chain one call per field and
+    finish with `build()`, e.g.
+    `Format::builder().time().literal(" [").level().literal("] ").tag().message().build()`.
+*/
+pub struct FormatBuilder {
+    tokens: Vec<FormatToken>,
+}
+
+impl Format {
+    /*
+        @brief Starts a new, empty format recipe.
+
+        @return FormatBuilder
+    */
+    pub fn builder() -> FormatBuilder {
+        FormatBuilder { tokens: Vec::new() }
+    }
+}
+
+impl FormatBuilder {
+    /*
+        @brief Append the timestamp field.
+
+        @return FormatBuilder
+    */
+    pub fn time(mut self) -> Self {
+        self.tokens.push(FormatToken::Time);
+        self
+    }
+
+    /*
+        @brief Append the padded, coloured level tag.
+
+        @return FormatBuilder
+    */
+    pub fn level(mut self) -> Self {
+        self.tokens.push(FormatToken::Level);
+        self
+    }
+
+    /*
+        @brief Append the coloured `[domain]` tag.
+
+        @return FormatBuilder
+    */
+    pub fn tag(mut self) -> Self {
+        self.tokens.push(FormatToken::Tag);
+        self
+    }
+
+    /*
+        @brief Append the coloured message.
+
+        @return FormatBuilder
+    */
+    pub fn message(mut self) -> Self {
+        self.tokens.push(FormatToken::Message);
+        self
+    }
+
+    /*
+        @brief Append the caller info inline (only rendered when `at` is set).
+
+        @return FormatBuilder
+    */
+    pub fn caller(mut self) -> Self {
+        self.tokens.push(FormatToken::Caller);
+        self
+    }
+
+    /*
+        @brief Append the tree connector glyph.
+
+        @return FormatBuilder
+    */
+    pub fn connector(mut self) -> Self {
+        self.tokens.push(FormatToken::Connector);
+        self
+    }
+
+    /*
+        @brief Append a literal string, verbatim.
+
+        @param text to insert between fields.
+
+        @return FormatBuilder
+    */
+    pub fn literal(mut self, text: &str) -> Self {
+        self.tokens.push(FormatToken::Literal(text.to_string()));
+        self
+    }
+
+    /*
+        @brief Finish building and hand back the `Format`.
+
+        @return Format
+    */
+    pub fn build(self) -> Format {
+        Format { tokens: self.tokens }
+    }
 }
 
 struct Connectors {
@@ -62,7 +370,21 @@ impl Default for Logger {
             _ => LogLevel::Info,
         };
 
-        Self { log_level: log_level, log_file: String::from("") }
+        Self {
+            log_level: log_level,
+            log_file:  String::from(""),
+            format:    None,
+            memory:    None,
+            sender:    None,
+            policy:    OverflowPolicy::Block,
+            dropped:   Arc::new(AtomicUsize::new(0)),
+            worker:    None,
+            rotation:  None,
+            tag_levels: HashMap::new(),
+            output:     LogFormat::Pretty,
+            #[cfg(all(unix, feature = "syslog"))]
+            syslog:     None,
+        }
     }
 }
 
@@ -70,10 +392,252 @@ impl Logger {
     pub fn new(log_level: LogLevel, log_file: Option<&str>) -> Self {
         Logger {
             log_level:  log_level,
-            log_file:   log_file.unwrap_or("").to_string()
+            log_file:   log_file.unwrap_or("").to_string(),
+            format:     None,
+            memory:     None,
+            sender:     None,
+            policy:     OverflowPolicy::Block,
+            dropped:    Arc::new(AtomicUsize::new(0)),
+            worker:     None,
+            rotation:   None,
+            tag_levels: HashMap::new(),
+            output:     LogFormat::Pretty,
+            #[cfg(all(unix, feature = "syslog"))]
+            syslog:     None,
         }
     }
 
+    /*
+        @brief Creates a logger that writes on a background thread.
+
+        Spawns a dedicated writer thread and hands it the open file handle
+        (opened once, not per line). The log methods serialize the object,
+        capture the caller and push a small owned job over a bounded channel,
+        returning immediately — formatting, ANSI-stripping and I/O all happen
+        off the hot path. Use `set_overflow_policy` to choose between blocking
+        and dropping when the channel is full, and `flush` (also run on `Drop`)
+        to drain buffered messages before exit.
+
+        @param log_level The threshold below which records are skipped.
+        @param log_file Optional file to write to instead of stdout.
+        @param bound The channel capacity.
+
+        @return Logger
+    */
+    pub fn new_async(log_level: LogLevel, log_file: Option<&str>, bound: usize) -> Self {
+        let log_file = log_file.unwrap_or("").to_string();
+        let (sender, receiver) = sync_channel::<Command>(bound);
+
+        let formatter = Logger {
+            log_level:  log_level,
+            log_file:   log_file.clone(),
+            format:     None,
+            memory:     None,
+            sender:     None,
+            policy:     OverflowPolicy::Block,
+            dropped:    Arc::new(AtomicUsize::new(0)),
+            worker:     None,
+            rotation:   None,
+            tag_levels: HashMap::new(),
+            output:     LogFormat::Pretty,
+            #[cfg(all(unix, feature = "syslog"))]
+            syslog:     None,
+        };
+
+        let worker = std::thread::spawn(move || {
+            let mut formatter = formatter;
+            let mut file = if !formatter.log_file.is_empty() {
+                OpenOptions::new()
+                    .write(true)
+                    .append(true)
+                    .create(true)
+                    .open(&formatter.log_file)
+                    .ok()
+            } else {
+                None
+            };
+
+            while let Ok(command) = receiver.recv() {
+                match command {
+                    Command::Multi(job) => {
+                        formatter.format = job.format;
+                        formatter.output = job.output;
+                        let log = match formatter.output {
+                            LogFormat::Json => formatter.build_json(&job.message, &job.tag, job.at, job.level, job.object.as_deref(), job.callee.as_deref()),
+                            LogFormat::Pretty => formatter.build_multi(&job.message, &job.tag, job.at, job.level, job.object.as_deref(), job.callee.as_deref()),
+                        };
+
+                        #[cfg(all(unix, feature = "syslog"))]
+                        if let Some(facility) = job.syslog {
+                            formatter.emit_syslog(facility, job.level, &log);
+                            continue;
+                        }
+
+                        formatter.emit_line(&mut file, &log);
+                    }
+                    Command::Single(job) => {
+                        formatter.format = job.format;
+                        formatter.output = job.output;
+                        let log = match formatter.output {
+                            LogFormat::Json => formatter.build_json(&job.message, &job.tag, false, job.level, None, None),
+                            LogFormat::Pretty => formatter.build_single(&job.message, &job.tag, job.level),
+                        };
+
+                        #[cfg(all(unix, feature = "syslog"))]
+                        if let Some(facility) = job.syslog {
+                            formatter.emit_syslog(facility, job.level, &log);
+                            continue;
+                        }
+
+                        formatter.emit_line(&mut file, &log);
+                    }
+                    Command::Flush(ack) => {
+                        if let Some(file) = file.as_mut() {
+                            let _ = file.flush();
+                        }
+                        let _ = ack.send(());
+                    }
+                }
+            }
+        });
+
+        Logger {
+            log_level:  log_level,
+            log_file:   log_file,
+            format:     None,
+            memory:     None,
+            sender:     Some(sender),
+            policy:     OverflowPolicy::Block,
+            dropped:    Arc::new(AtomicUsize::new(0)),
+            worker:     Some(worker),
+            rotation:   None,
+            tag_levels: HashMap::new(),
+            output:     LogFormat::Pretty,
+            #[cfg(all(unix, feature = "syslog"))]
+            syslog:     None,
+        }
+    }
+
+    /*
+        @brief Sets the channel-full policy for async mode.
+
+        @param policy `Block` to apply back-pressure, `Drop` to discard.
+
+        @return void
+    */
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.policy = policy;
+    }
+
+    /*
+        @brief Number of records dropped because the channel was full.
+
+        Always zero unless async mode is running with `OverflowPolicy::Drop`.
+
+        @return usize
+    */
+    pub fn dropped_count(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /*
+        @brief Hands a job to the background writer thread.
+
+        Honours the configured `OverflowPolicy`: blocks until there is room, or
+        drops the job and bumps the dropped counter.
+
+        @param command the job to enqueue.
+
+        @return void
+    */
+    fn dispatch(&self, command: Command) {
+        let sender = match &self.sender {
+            Some(sender) => sender,
+            None => return,
+        };
+
+        match self.policy {
+            OverflowPolicy::Block => {
+                let _ = sender.send(command);
+            }
+            OverflowPolicy::Drop => {
+                match sender.try_send(command) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(TrySendError::Disconnected(_)) => {}
+                }
+            }
+        }
+    }
+
+    /*
+        @brief Blocks until the writer thread has drained the channel.
+
+        Sends a flush marker and waits for the writer to acknowledge it; because
+        the channel is FIFO, every job queued before the call has been written
+        by the time this returns. A no-op in synchronous mode.
+
+        @return void
+    */
+    pub fn flush(&self) {
+        let sender = match &self.sender {
+            Some(sender) => sender,
+            None => return,
+        };
+
+        let (ack, done) = std::sync::mpsc::channel();
+        if sender.send(Command::Flush(ack)).is_ok() {
+            let _ = done.recv();
+        }
+    }
+
+    /*
+        @brief Writes a formatted line to a persistent handle or stdout.
+
+        The background-writer counterpart to `emit`: reuses the file handle the
+        writer thread opened once rather than reopening per line.
+
+        @param file the persistent file handle, if logging to a file.
+        @param log the formatted line to write.
+
+        @return void
+    */
+    fn emit_line(&self, file: &mut Option<std::fs::File>, log: &str) {
+        if let Some(file) = file.as_mut() {
+            let log = self.remove_ansi(log);
+            writeln!(file, "{}", log).unwrap();
+            return;
+        }
+
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        writeln!(handle, "{}", log).unwrap();
+    }
+
+    /*
+        @brief Enables the in-memory record store.
+
+        Retains the most recent entries so they can be retrieved with `query`
+        instead of only hitting stdout or a file. At most `capacity` records
+        are kept, and entries older than `keep` are evicted on the next write.
+
+        @param capacity maximum number of records to retain.
+        @param keep maximum age of a retained record.
+
+        @return Logger
+    */
+    pub fn with_memory(capacity: usize, keep: Duration) -> Self {
+        let mut logger = Logger::default();
+        logger.memory = Some(Memory {
+            buffer:   Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity,
+            keep:     keep,
+        });
+        logger
+    }
+
     /*
         @brief Grabs the correlating tag.
 
@@ -197,27 +761,143 @@ impl Logger {
 
 
     /*
-        @brief Writes the data to the file or terminal.
+        @brief Renders a custom `Format` into the main log line.
 
-        Serializes all data that gets appended within the object, allowing for ease of
-        printing to the console or file. The method checks the log level and formats the
-        message accordingly, including the timestamp, log level, and other metadata.
+        Walks the configured tokens in order, reusing the exact same colour and
+        padding logic as the default layout so a bespoke format looks at home
+        next to the built-in one. The `connector` argument lets the single-line
+        and multi-line writers share this routine.
 
-        @param message The message to log.
-        @param tag A tag for categorizing the log entry.
-        @param at Whether to include caller information.
-        @param level The log level of the message.
-        @param object Optional object to serialize and log.
+        @param format the ordered token recipe.
+        @param level the log level of this record.
+        @param tag the domain tag.
+        @param message the message to log.
+        @param connector the tree glyph to use for `Connector` tokens.
+        @param callee the caller info for `Caller` tokens; `None` renders nothing.
+
+        @return String
+    */
+    fn render_format(&self, format: &Format, level: &LogLevel, tag: &str, message: &str, connector: &str, callee: Option<&str>) -> String {
+        let color = self.get_colour(level);
+        let mut log = String::new();
+
+        for token in &format.tokens {
+            match token {
+                FormatToken::Time      => log.push_str(&self.timestamp()),
+                FormatToken::Level     => log.push_str(&self.get_tag(level).pad_to_width_with_alignment(6, Alignment::Middle)),
+                FormatToken::Tag       => log.push_str(&format!("[{}]", tag.color(color))),
+                FormatToken::Message   => log.push_str(&message.color(color).to_string()),
+                FormatToken::Caller    => if let Some(callee) = callee { log.push_str(&callee.dimmed().to_string()) },
+                FormatToken::Connector => log.push_str(connector),
+                FormatToken::Literal(text) => log.push_str(text),
+            }
+        }
+
+        log
+    }
+
+    /*
+        @brief Pushes a record into the in-memory store.
+
+        No-op unless `with_memory` enabled the store. After pushing the new
+        record, evicts anything older than `keep` or beyond `capacity`.
+
+        @param record the uncoloured entry to retain.
 
         @return void
     */
-    fn write<T: Serialize + 'static>(&self, message: &str, tag: &str, at: bool, level: LogLevel, object: Option<&T>) {
-        if (level as i32) > (self.log_level as i32) {
-            return;
+    fn remember(&self, record: LogRecord) {
+        let memory = match &self.memory {
+            Some(memory) => memory,
+            None => return,
+        };
+
+        let mut buffer = memory.buffer.lock().unwrap();
+        buffer.push_back(record);
+
+        let cutoff = Local::now() - memory.keep;
+        while buffer.front().map(|r| r.timestamp < cutoff).unwrap_or(false) {
+            buffer.pop_front();
         }
+        while buffer.len() > memory.capacity {
+            buffer.pop_front();
+        }
+    }
 
-        let message = message.to_string();
-        let tag = tag.to_string();
+    /*
+        @brief Queries the retained records.
+
+        Walks the store newest-first and returns up to `filter.limit` matches in
+        reverse chronological order. A record matches when its level is at least
+        as severe as `filter.level` and it satisfies every supplied constraint
+        (`module`, `regex`, `not_before`). Returns an empty vector when the
+        in-memory store is disabled.
+
+        @param filter the constraints to match against.
+
+        @return Vec<LogRecord>
+    */
+    pub fn query(&self, filter: &RecordFilter) -> Vec<LogRecord> {
+        let memory = match &self.memory {
+            Some(memory) => memory,
+            None => return Vec::new(),
+        };
+
+        let buffer = memory.buffer.lock().unwrap();
+        let mut matches = Vec::new();
+
+        for record in buffer.iter().rev() {
+            if matches.len() as u32 >= filter.limit {
+                break;
+            }
+
+            if (record.level as i32) > (filter.level as i32) {
+                continue;
+            }
+
+            if let Some(module) = &filter.module {
+                if &record.tag != module {
+                    continue;
+                }
+            }
+
+            if let Some(regex) = &filter.regex {
+                if !regex.is_match(&record.message) {
+                    continue;
+                }
+            }
+
+            if let Some(not_before) = filter.not_before {
+                if record.timestamp < not_before {
+                    continue;
+                }
+            }
+
+            matches.push(record.clone());
+        }
+
+        matches
+    }
+
+    /*
+        @brief Builds the full multi-line tree for a record.
+
+        Factored out of `write` so the synchronous path and the background
+        writer thread render identical output. Takes the already-serialized
+        object and the already-captured caller string (the caller must be
+        captured on the originating thread, since it relies on the live
+        backtrace) rather than recomputing them here.
+
+        @param message The message to log.
+        @param tag A tag for categorizing the log entry.
+        @param at Whether caller information should be shown.
+        @param level The log level of the message.
+        @param serialized Optional pre-serialized object.
+        @param callee Optional pre-captured caller string.
+
+        @return String
+    */
+    fn build_multi(&self, message: &str, tag: &str, at: bool, level: LogLevel, serialized: Option<&str>, callee: Option<&str>) -> String {
         let connectors = &Connectors::default();
         let color = self.get_colour(&level);
         let timestamp = self.timestamp();
@@ -226,26 +906,36 @@ impl Logger {
         let level_tag = self.get_tag(&level).pad_to_width_with_alignment(6, Alignment::Middle);
         let domain_tag = format!("[{}]", tag.color(color));
         let main_message = message.color(color);
-        let mut log = format!(
-            "{} {} {} {} {}",
-            timestamp, level_tag, connectors.start_line, domain_tag, main_message
-        );
 
-        let meta_lines: Vec<String> = if let Some(obj) = object {
-            vec![self.serialize(obj)]
+        let caller_inline = self.format.as_ref()
+            .map(|f| f.tokens.iter().any(|t| matches!(t, FormatToken::Caller)))
+            .unwrap_or(false);
+
+        let mut log = if let Some(format) = &self.format {
+            self.render_format(format, &level, tag, message, connectors.start_line, if at { callee } else { None })
         } else {
-            vec![]
+            format!(
+                "{} {} {} {} {}",
+                timestamp, level_tag, connectors.start_line, domain_tag, main_message
+            )
         };
 
-        if at {
-            let callee = self.get_callee().dimmed();
-            log.push_str(&format!(
-                "\n{} {} {} {}",
-                timestamp_padding,
-                dim_level_tag,
-                if !meta_lines.is_empty() { connectors.line } else { connectors.end_line },
-                callee
-            ));
+        let meta_lines: Vec<String> = match serialized {
+            Some(obj) => vec![obj.to_string()],
+            None => vec![],
+        };
+
+        if at && !caller_inline {
+            if let Some(callee) = callee {
+                let callee = callee.dimmed();
+                log.push_str(&format!(
+                    "\n{} {} {} {}",
+                    timestamp_padding,
+                    dim_level_tag,
+                    if !meta_lines.is_empty() { connectors.line } else { connectors.end_line },
+                    callee
+                ));
+            }
         }
 
         for (i, line) in meta_lines.iter().enumerate() {
@@ -257,9 +947,121 @@ impl Logger {
                 timestamp_padding, dim_level_tag, connector, line_number, line_content
             ));
         }
-        
+
+        log
+    }
+
+    /*
+        @brief Builds the single-line layout for a record.
+
+        The `write_single` counterpart to `build_multi`, shared with the
+        background writer thread.
+
+        @param message The message to log.
+        @param tag A tag for categorizing the log entry.
+        @param level The log level of the message.
+
+        @return String
+    */
+    fn build_single(&self, message: &str, tag: &str, level: LogLevel) -> String {
+        let connectors = &Connectors::default();
+        let color = self.get_colour(&level);
+        let timestamp = self.timestamp();
+        let level_tag = self.get_tag(&level).pad_to_width_with_alignment(6, Alignment::Middle);
+        let domain_tag = format!("[{}]", tag.color(color));
+        let main_message = message.color(color);
+
+        if let Some(format) = &self.format {
+            self.render_format(format, &level, tag, message, connectors.single_line, None)
+        } else {
+            format!(
+                "{} {} {} {} {}",
+                timestamp, level_tag, connectors.single_line, domain_tag, main_message
+            )
+        }
+    }
+
+    /*
+        @brief The lowercase name of a level, for machine output.
+
+        @param level the level to name.
+
+        @return &'static str
+    */
+    fn level_name(&self, level: &LogLevel) -> &'static str {
+        match level {
+            LogLevel::Silly => "silly",
+            LogLevel::Debug => "debug",
+            LogLevel::Info  => "info",
+            LogLevel::Warn  => "warn",
+            LogLevel::Error => "error",
+            LogLevel::Fatal => "fatal",
+        }
+    }
+
+    /*
+        @brief Builds one JSON object for a record.
+
+        Emits `{"ts","level","tag","msg"}` plus a `caller` field when `at` is
+        set and a nested `data` value when an object was supplied. The object is
+        embedded as real JSON rather than a pre-stringified blob so downstream
+        aggregators can consume it without a second parse.
+
+        @param message The message to log.
+        @param tag A tag for categorizing the log entry.
+        @param at Whether caller information should be included.
+        @param level The log level of the message.
+        @param serialized Optional pre-serialized object (valid JSON).
+        @param callee Optional pre-captured caller string.
+
+        @return String
+    */
+    fn build_json(&self, message: &str, tag: &str, at: bool, level: LogLevel, serialized: Option<&str>, callee: Option<&str>) -> String {
+        let mut object = serde_json::Map::new();
+        object.insert("ts".to_string(), Value::String(Local::now().to_rfc3339()));
+        object.insert("level".to_string(), Value::String(self.level_name(&level).to_string()));
+        object.insert("tag".to_string(), Value::String(tag.to_string()));
+        object.insert("msg".to_string(), Value::String(message.to_string()));
+
+        if at {
+            if let Some(callee) = callee {
+                object.insert("caller".to_string(), Value::String(self.remove_ansi(callee)));
+            }
+        }
+
+        if let Some(serialized) = serialized {
+            let data = serde_json::from_str::<Value>(serialized).unwrap_or_else(|_| Value::String(serialized.to_string()));
+            object.insert("data".to_string(), data);
+        }
+
+        Value::Object(object).to_string()
+    }
+
+    /*
+        @brief Emits a fully-formatted line to the file or terminal.
+
+        Used by the synchronous path; opens the log file fresh per call, just
+        as the original code did. The background writer thread keeps its own
+        persistent handle instead.
+
+        @param log The formatted line to emit.
+
+        @return void
+    */
+    fn emit(&self, log: &str) {
         if !self.log_file.is_empty() {
-            let log = self.remove_ansi(&log);
+            let log = self.remove_ansi(log);
+            let line_len = log.len() as u64 + 1;
+
+            if let Some(rotation) = &self.rotation {
+                let mut size = rotation.size.lock().unwrap();
+                if *size > 0 && *size + line_len > rotation.max_bytes {
+                    self.rotate_files(rotation);
+                    *size = 0;
+                }
+                *size += line_len;
+            }
+
             let file = OpenOptions::new()
                     .write(true)
                     .append(true)
@@ -281,6 +1083,92 @@ impl Logger {
         let mut handle = stdout.lock();
         writeln!(handle, "{}", log).unwrap();
     }
+
+    /*
+        @brief Rolls the log file over, newest to oldest.
+
+        Deletes `app.log.{max_files}`, shifts each `app.log.N` up to
+        `app.log.{N+1}`, then renames the live file to `app.log.1`, leaving a
+        fresh name for the next write to create.
+
+        @param rotation the active rotation settings.
+
+        @return void
+    */
+    fn rotate_files(&self, rotation: &Rotation) {
+        let path = &self.log_file;
+
+        let _ = std::fs::remove_file(format!("{}.{}", path, rotation.max_files));
+
+        for i in (1..rotation.max_files).rev() {
+            let from = format!("{}.{}", path, i);
+            if std::fs::metadata(&from).is_ok() {
+                let _ = std::fs::rename(&from, format!("{}.{}", path, i + 1));
+            }
+        }
+
+        let _ = std::fs::rename(path, format!("{}.1", path));
+    }
+
+
+    /*
+        @brief Writes the data to the file or terminal.
+
+        Serializes all data that gets appended within the object, allowing for ease of
+        printing to the console or file. The method checks the log level and formats the
+        message accordingly, including the timestamp, log level, and other metadata.
+
+        @param message The message to log.
+        @param tag A tag for categorizing the log entry.
+        @param at Whether to include caller information.
+        @param level The log level of the message.
+        @param object Optional object to serialize and log.
+
+        @return void
+    */
+    fn write<T: Serialize + 'static>(&self, message: &str, tag: &str, at: bool, level: LogLevel, object: Option<&T>) {
+        if (level as i32) > (self.threshold_for(tag) as i32) {
+            return;
+        }
+
+        let message = message.to_string();
+        let tag = tag.to_string();
+        let serialized = object.map(|obj| self.serialize(obj));
+        let callee = if at { Some(self.get_callee()) } else { None };
+
+        self.remember(LogRecord {
+            timestamp: Local::now(),
+            level:     level,
+            tag:       tag.clone(),
+            message:   message.clone(),
+            object:    serialized.clone(),
+            callee:    callee.as_ref().map(|c| self.remove_ansi(c)),
+        });
+
+        if self.sender.is_some() {
+            self.dispatch(Command::Multi(MultiJob {
+                message, tag, level, at, object: serialized, callee,
+                format: self.format.clone(),
+                output: self.output,
+                #[cfg(all(unix, feature = "syslog"))]
+                syslog: self.syslog.as_ref().map(|s| s.facility),
+            }));
+            return;
+        }
+
+        let log = match self.output {
+            LogFormat::Json => self.build_json(&message, &tag, at, level, serialized.as_deref(), callee.as_deref()),
+            LogFormat::Pretty => self.build_multi(&message, &tag, at, level, serialized.as_deref(), callee.as_deref()),
+        };
+
+        #[cfg(all(unix, feature = "syslog"))]
+        if let Some(syslog) = &self.syslog {
+            self.emit_syslog(syslog.facility, level, &log);
+            return;
+        }
+
+        self.emit(&log);
+    }
     
     /*
         @brief Writes the data to the file or terminal.
@@ -295,45 +1183,45 @@ impl Logger {
         @return void
     */
     fn write_single(&self, message: &str, tag: &str, level: LogLevel)  {
-        if (level as i32) > (self.log_level as i32) {
+        if (level as i32) > (self.threshold_for(tag) as i32) {
             return;
         }
 
         let message = message.to_string();
         let tag = tag.to_string();
-        let connectors = &Connectors::default();
-        let color = self.get_colour(&level);
-        let timestamp = self.timestamp();
-        let level_tag = self.get_tag(&level).pad_to_width_with_alignment(6, Alignment::Middle);
-        let domain_tag = format!("[{}]", tag.color(color));
-        let main_message = message.color(color);
-        let log = format!(
-            "{} {} {} {} {}",
-            timestamp, level_tag, connectors.single_line, domain_tag, main_message
-        );
 
-        if !self.log_file.is_empty() {
-            let log = self.remove_ansi(&log);
-            let file = OpenOptions::new()
-                            .write(true)
-                            .append(true)
-                            .create(true)
-                            .open(&self.log_file);
+        self.remember(LogRecord {
+            timestamp: Local::now(),
+            level:     level,
+            tag:       tag.clone(),
+            message:   message.clone(),
+            object:    None,
+            callee:    None,
+        });
+
+        if self.sender.is_some() {
+            self.dispatch(Command::Single(SingleJob {
+                message, tag, level,
+                format: self.format.clone(),
+                output: self.output,
+                #[cfg(all(unix, feature = "syslog"))]
+                syslog: self.syslog.as_ref().map(|s| s.facility),
+            }));
+            return;
+        }
 
-            match file {
-                Ok(mut file) => {
-                    writeln!(file, "{}", log).unwrap();
-                }
-                Err(error) => {
-                    eprint!("Failed to write to log file: {}", error);
-                }
-            }
+        let log = match self.output {
+            LogFormat::Json => self.build_json(&message, &tag, false, level, None, None),
+            LogFormat::Pretty => self.build_single(&message, &tag, level),
+        };
+
+        #[cfg(all(unix, feature = "syslog"))]
+        if let Some(syslog) = &self.syslog {
+            self.emit_syslog(syslog.facility, level, &log);
             return;
         }
 
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
-        writeln!(handle, "{}", log).unwrap();
+        self.emit(&log);
     }
 
     /*
@@ -350,6 +1238,53 @@ impl Logger {
        self.log_file = file_name.to_string();
     }
 
+    /*
+        @brief Sets a size-rotated log file.
+
+        Like `set_file`, but caps the file at `max_bytes` and keeps up to
+        `max_files` older copies (`app.log.1` … `app.log.{max_files}`). The
+        current size is read once here and then tracked in memory, so ordinary
+        writes avoid a `metadata()` syscall per line.
+
+        @param path The file to write to.
+        @param max_bytes The size at which the file is rolled over.
+        @param max_files How many rotated copies to keep.
+
+        @return void
+    */
+    pub fn set_file_rotating(&mut self, path: &str, max_bytes: u64, max_files: u32) {
+        self.log_file = path.to_string();
+        let initial = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        self.rotation = Some(Rotation { max_bytes, max_files, size: Mutex::new(initial) });
+    }
+
+    /*
+        @brief Sets a custom line format.
+
+        Stores a `Format` built with `Format::builder()`, which `write` and
+        `write_single` consume in place of the default layout. Clear it again
+        with `remove_format` to fall back to the built-in tree style.
+
+        @param format The composed format to use.
+
+        @return void
+    */
+    pub fn set_format(&mut self, format: Format) {
+        self.format = Some(format);
+    }
+
+    /*
+        @brief Clears any custom line format.
+
+        Returns the logger to the default `{timestamp} {level} {connector} {tag} {message}`
+        layout.
+
+        @return void
+    */
+    pub fn remove_format(&mut self) {
+        self.format = None;
+    }
+
     /*
         @brief Removes the log_file name
 
@@ -360,6 +1295,7 @@ impl Logger {
     */
     pub fn remove_file(&mut self) {
         self.log_file = String::from("");
+        self.rotation = None;
     }
 
     /*
@@ -376,6 +1312,127 @@ impl Logger {
         self.log_level = log_level;
     }
 
+    /*
+        @brief Override the log level for a single tag
+
+        Sets a per-tag (per-"domain") threshold so one subsystem can be made
+        noisier or quieter than the rest of the app. Records carrying this tag
+        are gated against the given level instead of the global one.
+
+        @param tag The domain tag to override.
+        @param level The LogLevel to apply to that tag.
+
+        @return void
+    */
+    pub fn set_tag_level(&mut self, tag: &str, level: LogLevel) {
+        self.tag_levels.insert(tag.to_string(), level);
+    }
+
+    /*
+        @brief Choose between pretty and JSON output
+
+        In `LogFormat::Json` mode `write`/`write_single` emit one JSON object
+        per line instead of the ANSI tree, suitable for machine ingestion.
+
+        @param output The output format to use.
+
+        @return void
+    */
+    pub fn set_log_format(&mut self, output: LogFormat) {
+        self.output = output;
+    }
+
+    /*
+        @brief Route records to the local syslog daemon.
+
+        Opens a connection under `ident` and forwards every subsequent record to
+        syslog with its level mapped onto a priority, replacing the terminal as
+        the sink. `facility` is one of the `libc::LOG_*` facilities masked into
+        each priority. Only compiled on Unix with the `syslog` feature.
+
+        @param ident The program identity passed to `openlog`.
+        @param facility The syslog facility masked into every priority.
+
+        @return void
+    */
+    #[cfg(all(unix, feature = "syslog"))]
+    pub fn set_syslog(&mut self, ident: &str, facility: libc::c_int) {
+        let ident = std::ffi::CString::new(ident).expect("syslog ident contained a NUL byte");
+        unsafe {
+            libc::openlog(ident.as_ptr(), libc::LOG_PID | libc::LOG_CONS, facility);
+        }
+        self.syslog = Some(Syslog { ident, facility });
+    }
+
+    /*
+        @brief Maps a level onto a syslog priority.
+
+        Combines the configured facility with the severity each level implies,
+        following the conventional `Fatal`→`CRIT` … `Silly`→`DEBUG` ladder.
+
+        @param facility The syslog facility masked into the priority.
+        @param level The level of the record.
+
+        @return libc::c_int
+    */
+    #[cfg(all(unix, feature = "syslog"))]
+    fn syslog_priority(&self, facility: libc::c_int, level: LogLevel) -> libc::c_int {
+        let severity = match level {
+            LogLevel::Fatal => libc::LOG_CRIT,
+            LogLevel::Error => libc::LOG_ERR,
+            LogLevel::Warn  => libc::LOG_WARNING,
+            LogLevel::Info  => libc::LOG_INFO,
+            LogLevel::Debug => libc::LOG_DEBUG,
+            LogLevel::Silly => libc::LOG_DEBUG,
+        };
+
+        facility | severity
+    }
+
+    /*
+        @brief Sends one formatted line to syslog.
+
+        Strips the ANSI escapes into the thread-local scratch buffer, then hands
+        the text to `syslog(3)` through a `%s` template so any stray percent
+        signs in the message are treated literally.
+
+        @param facility The syslog facility masked into the priority.
+        @param level The level of the record.
+        @param log The fully-formatted line.
+
+        @return void
+    */
+    #[cfg(all(unix, feature = "syslog"))]
+    fn emit_syslog(&self, facility: libc::c_int, level: LogLevel, log: &str) {
+        let priority = self.syslog_priority(facility, level);
+
+        SYSLOG_BUF.with(|cell| {
+            let mut buffer = cell.borrow_mut();
+            buffer.clear();
+            buffer.push_str(&self.remove_ansi(log));
+
+            if let Ok(line) = std::ffi::CString::new(buffer.as_str()) {
+                unsafe {
+                    libc::syslog(priority, b"%s\0".as_ptr() as *const libc::c_char, line.as_ptr());
+                }
+            }
+        });
+    }
+
+    /*
+        @brief Resolve the effective threshold for a tag
+
+        Consults the per-tag overrides first and falls back to the global
+        `log_level` when the tag has none.
+
+        @param tag The domain tag being logged.
+
+        @return LogLevel
+    */
+    fn threshold_for(&self, tag: &str) -> LogLevel {
+        self.tag_levels.get(tag).copied().unwrap_or(self.log_level)
+    }
+
     /*
         @brief Logs to the terminal, or file using the tag fatal.
 
@@ -572,4 +1629,118 @@ impl Logger {
     pub fn fatal_single(&self, message: &str, tag: &str)   {
         self.write_single(message, tag, LogLevel::Fatal)
     }
-}
\ No newline at end of file
+}
+
+/*
+    @brief Drains and joins the background writer on drop.
+
+    In async mode this flushes any buffered messages, closes the channel so the
+    writer thread can exit, and waits for it to finish so nothing is lost at
+    exit. A no-op in synchronous mode.
+*/
+impl Drop for Logger {
+    fn drop(&mut self) {
+        self.flush();
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: LogLevel, tag: &str, message: &str, timestamp: DateTime<Local>) -> LogRecord {
+        LogRecord {
+            timestamp: timestamp,
+            level:     level,
+            tag:       tag.to_string(),
+            message:   message.to_string(),
+            object:    None,
+            callee:    None,
+        }
+    }
+
+    fn filter(level: LogLevel, limit: u32) -> RecordFilter {
+        RecordFilter {
+            level:      level,
+            module:     None,
+            regex:      None,
+            not_before: None,
+            limit:      limit,
+        }
+    }
+
+    #[test]
+    fn memory_evicts_beyond_capacity() {
+        let logger = Logger::with_memory(3, Duration::hours(1));
+        let now = Local::now();
+        for i in 0..5 {
+            logger.remember(record(LogLevel::Info, "Main", &format!("msg{}", i), now + Duration::seconds(i)));
+        }
+
+        let found = logger.query(&filter(LogLevel::Silly, 100));
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].message, "msg4");
+        assert_eq!(found[2].message, "msg2");
+    }
+
+    #[test]
+    fn memory_evicts_entries_older_than_keep() {
+        let logger = Logger::with_memory(10, Duration::minutes(1));
+        let now = Local::now();
+        logger.remember(record(LogLevel::Info, "Main", "stale", now - Duration::hours(1)));
+        logger.remember(record(LogLevel::Info, "Main", "fresh", now));
+
+        let found = logger.query(&filter(LogLevel::Silly, 100));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].message, "fresh");
+    }
+
+    #[test]
+    fn query_returns_newest_first() {
+        let logger = Logger::with_memory(10, Duration::hours(1));
+        let now = Local::now();
+        logger.remember(record(LogLevel::Info, "Main", "first", now));
+        logger.remember(record(LogLevel::Info, "Main", "second", now + Duration::seconds(1)));
+        logger.remember(record(LogLevel::Info, "Main", "third", now + Duration::seconds(2)));
+
+        let found = logger.query(&filter(LogLevel::Silly, 100));
+        let messages: Vec<&str> = found.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["third", "second", "first"]);
+    }
+
+    #[test]
+    fn query_honours_level_module_regex_and_limit() {
+        let logger = Logger::with_memory(10, Duration::hours(1));
+        let now = Local::now();
+        logger.remember(record(LogLevel::Debug, "Main", "debug-noise", now));
+        logger.remember(record(LogLevel::Error, "Net", "connection reset", now + Duration::seconds(1)));
+        logger.remember(record(LogLevel::Warn, "Main", "disk almost full", now + Duration::seconds(2)));
+        logger.remember(record(LogLevel::Error, "Main", "disk write failed", now + Duration::seconds(3)));
+
+        // Level threshold: Warn keeps Warn/Error/Fatal but drops the Debug record.
+        let mut warn = filter(LogLevel::Warn, 100);
+        let found = logger.query(&warn);
+        assert_eq!(found.len(), 3);
+        assert!(found.iter().all(|r| (r.level as i32) <= (LogLevel::Warn as i32)));
+
+        // Module restricts to a single tag.
+        warn.module = Some("Main".to_string());
+        let found = logger.query(&warn);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|r| r.tag == "Main"));
+
+        // Regex is matched against the plain message.
+        warn.regex = Some(Regex::new("disk").unwrap());
+        let found = logger.query(&warn);
+        assert_eq!(found.len(), 2);
+
+        // Limit caps the newest matches.
+        warn.limit = 1;
+        let found = logger.query(&warn);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].message, "disk write failed");
+    }
+}